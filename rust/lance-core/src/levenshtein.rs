@@ -1,53 +1,153 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-FileCopyrightText: Copyright The Lance Authors
 
-/// Calculate the Levenshtein distance between two strings.
+/// Shared row-sweep DP behind [`levenshtein_distance`], [`damerau_levenshtein_distance`],
+/// and [`damerau_levenshtein_distance_limit`].
 ///
-/// The Levenshtein distance is a measure of the number of single-character edits
-/// (insertions, deletions, or substitutions) required to change one word into the other.
-///
-/// # Examples
+/// `allow_transposition` switches on the extra three-row recurrence that lets
+/// an adjacent transposition count as a single edit (Damerau-Levenshtein);
+/// when `false` this is plain Levenshtein distance.
 ///
-/// ```
-/// use lance_core::levenshtein::levenshtein_distance;
-///
-/// assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
-/// assert_eq!(levenshtein_distance("hello", "hello"), 0);
-/// ```
-pub fn levenshtein_distance(s1: &str, s2: &str) -> usize {
+/// `limit` switches on early-bail pruning: once the length-diff lower bound
+/// or a completed row's minimum exceeds `limit`, the true distance is
+/// provably `> limit` and we return `None` without finishing the sweep. Pass
+/// `None` to always compute the exact distance.
+fn edit_distance(
+    s1: &str,
+    s2: &str,
+    allow_transposition: bool,
+    limit: Option<usize>,
+) -> Option<usize> {
     let s1_chars: Vec<char> = s1.chars().collect();
     let s2_chars: Vec<char> = s2.chars().collect();
     let m = s1_chars.len();
     let n = s2_chars.len();
 
+    // The difference in lengths is always a lower bound on the distance.
+    if let Some(limit) = limit {
+        if m.abs_diff(n) > limit {
+            return None;
+        }
+    }
+
     if m == 0 {
-        return n;
+        return match limit {
+            Some(limit) => (n <= limit).then_some(n),
+            None => Some(n),
+        };
     }
     if n == 0 {
-        return m;
+        return match limit {
+            Some(limit) => (m <= limit).then_some(m),
+            None => Some(m),
+        };
     }
 
-    // Use two rows instead of full matrix for space efficiency
+    // Three rows instead of a full matrix: the current row, the previous
+    // row, and the row from two iterations ago (the last is only read when
+    // `allow_transposition` is set, to detect a transposition of the
+    // previous two characters).
+    let mut prev_prev_row: Vec<usize> = vec![0; n + 1];
     let mut prev_row: Vec<usize> = (0..=n).collect();
     let mut curr_row: Vec<usize> = vec![0; n + 1];
 
     for (i, s1_char) in s1_chars.iter().enumerate() {
         curr_row[0] = i + 1;
+        let mut row_min = curr_row[0];
         for (j, s2_char) in s2_chars.iter().enumerate() {
             let cost = if s1_char == s2_char { 0 } else { 1 };
-            curr_row[j + 1] = (prev_row[j + 1] + 1)
+            let mut value = (prev_row[j + 1] + 1)
                 .min(curr_row[j] + 1)
                 .min(prev_row[j] + cost);
+
+            if allow_transposition
+                && i > 0
+                && j > 0
+                && *s1_char == s2_chars[j - 1]
+                && s1_chars[i - 1] == *s2_char
+            {
+                value = value.min(prev_prev_row[j - 1] + 1);
+            }
+
+            curr_row[j + 1] = value;
+            row_min = row_min.min(value);
         }
+        if let Some(limit) = limit {
+            if row_min > limit {
+                return None;
+            }
+        }
+        std::mem::swap(&mut prev_prev_row, &mut prev_row);
         std::mem::swap(&mut prev_row, &mut curr_row);
     }
 
-    prev_row[n]
+    match limit {
+        Some(limit) => (prev_row[n] <= limit).then_some(prev_row[n]),
+        None => Some(prev_row[n]),
+    }
+}
+
+/// Calculate the Levenshtein distance between two strings.
+///
+/// The Levenshtein distance is a measure of the number of single-character edits
+/// (insertions, deletions, or substitutions) required to change one word into the other.
+///
+/// # Examples
+///
+/// ```
+/// use lance_core::levenshtein::levenshtein_distance;
+///
+/// assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+/// assert_eq!(levenshtein_distance("hello", "hello"), 0);
+/// ```
+pub fn levenshtein_distance(s1: &str, s2: &str) -> usize {
+    edit_distance(s1, s2, false, None).expect("unlimited computation always returns a distance")
+}
+
+/// Calculate the restricted Damerau-Levenshtein distance (optimal string
+/// alignment) between two strings.
+///
+/// This extends [`levenshtein_distance`] with one additional edit:
+/// transposing two adjacent characters counts as a single edit rather than
+/// two substitutions. This matches common keyboard typos (`vetcor` vs
+/// `vector`) that would otherwise be penalized twice under plain Levenshtein
+/// distance.
+///
+/// # Examples
+///
+/// ```
+/// use lance_core::levenshtein::damerau_levenshtein_distance;
+///
+/// assert_eq!(damerau_levenshtein_distance("vector", "vextor"), 1);
+/// assert_eq!(damerau_levenshtein_distance("kitten", "sitting"), 3);
+/// ```
+pub fn damerau_levenshtein_distance(s1: &str, s2: &str) -> usize {
+    edit_distance(s1, s2, true, None).expect("unlimited computation always returns a distance")
 }
 
-/// Find the best suggestion from a list of options based on Levenshtein distance.
+/// Calculate the restricted Damerau-Levenshtein distance between two strings,
+/// bailing out early once it is provable that the distance exceeds `limit`.
+///
+/// Returns `Some(distance)` if the true distance is `<= limit`, otherwise
+/// `None`. This avoids the full O(n * m) sweep when the caller only cares
+/// whether a candidate is within a small threshold, which is the common case
+/// for "did you mean" style suggestions against a large set of options.
+///
+/// # Examples
 ///
-/// Returns `Some(suggestion)` if there's an option where the Levenshtein distance
+/// ```
+/// use lance_core::levenshtein::damerau_levenshtein_distance_limit;
+///
+/// assert_eq!(damerau_levenshtein_distance_limit("vector", "vextor", 1), Some(1));
+/// assert_eq!(damerau_levenshtein_distance_limit("kitten", "sitting", 2), None);
+/// ```
+pub fn damerau_levenshtein_distance_limit(s1: &str, s2: &str, limit: usize) -> Option<usize> {
+    edit_distance(s1, s2, true, Some(limit))
+}
+
+/// Find the best suggestion from a list of options based on Damerau-Levenshtein distance.
+///
+/// Returns `Some(suggestion)` if there's an option where the Damerau-Levenshtein distance
 /// is at most 1/3 of the length of the input string (integer division).
 /// Otherwise returns `None`.
 ///
@@ -70,22 +170,120 @@ pub fn find_best_suggestion<'a, 'b>(
     }
 
     let threshold = input_len / 3;
-    let mut best_option: Option<(&'b str, usize)> = None;
+    find_suggestions(input, options, 1, threshold)
+        .into_iter()
+        .next()
+}
+
+/// Find up to `limit` suggestions from a list of options, ranked by
+/// Damerau-Levenshtein distance to `input`.
+///
+/// Only options with distance `<= max_distance` are considered; this uses
+/// [`damerau_levenshtein_distance_limit`] so the scan bails out per-candidate
+/// as soon as an option is provably too far away, rather than always
+/// computing its full distance. Results are sorted ascending by distance,
+/// with ties broken by the option's original order in `options` (this is a
+/// stable sort, not a re-ranking), so callers get a deterministic "did you
+/// mean" list.
+///
+/// # Examples
+///
+/// ```
+/// use lance_core::levenshtein::find_suggestions;
+///
+/// let options = vec!["vector", "vendor", "id", "name"];
+/// assert_eq!(find_suggestions("vecor", &options, 2, 2), vec!["vector", "vendor"]);
+/// assert_eq!(find_suggestions("vecor", &options, 1, 2), vec!["vector"]);
+/// ```
+pub fn find_suggestions<'b>(
+    input: &str,
+    options: &'b [impl AsRef<str>],
+    limit: usize,
+    max_distance: usize,
+) -> Vec<&'b str> {
+    let mut scored: Vec<(usize, &'b str)> = options
+        .iter()
+        .filter_map(|option| {
+            let option = option.as_ref();
+            let distance = damerau_levenshtein_distance_limit(input, option, max_distance)?;
+            Some((distance, option))
+        })
+        .collect();
+
+    // `sort_by_key` is stable, so options that tie on distance keep their
+    // original relative order.
+    scored.sort_by_key(|(distance, _)| *distance);
+
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, option)| option)
+        .collect()
+}
+
+/// Find the best suggestion from a list of options using heuristics that
+/// plain edit distance misses: case differences and truncated abbreviations.
+///
+/// This tries, in order:
+/// 1. An ASCII case-insensitive exact match (e.g. `Vector` vs `vector`),
+///    returned immediately as a distance-0 match.
+/// 2. A substring relationship once both strings are lowercased (e.g. `vec`
+///    vs `vector`), treated as a near-match: it is returned ahead of any
+///    edit-distance match, and among several such options the one whose
+///    length is closest to the input's wins (the earliest on ties).
+/// 3. Otherwise, [`find_best_suggestion`]'s usual Damerau-Levenshtein
+///    threshold comparison.
+///
+/// # Examples
+///
+/// ```
+/// use lance_core::levenshtein::find_best_suggestion_smart;
+///
+/// let options = vec!["vector", "id", "name"];
+/// assert_eq!(find_best_suggestion_smart("Vector", &options), Some("vector"));
+/// assert_eq!(find_best_suggestion_smart("vec", &options), Some("vector"));
+/// assert_eq!(find_best_suggestion_smart("vacter", &options), Some("vector"));
+/// ```
+pub fn find_best_suggestion_smart<'a, 'b>(
+    input: &'a str,
+    options: &'b [impl AsRef<str>],
+) -> Option<&'b str> {
+    let input_len = input.chars().count();
+    if input_len == 0 {
+        return None;
+    }
+
+    if let Some(option) = options
+        .iter()
+        .map(|option| option.as_ref())
+        .find(|option| option.eq_ignore_ascii_case(input))
+    {
+        return Some(option);
+    }
+
+    let input_lower = input.to_ascii_lowercase();
+    let mut best_substring_match: Option<(&'b str, usize)> = None;
     for option in options {
-        let distance = levenshtein_distance(input, option.as_ref());
-        if distance <= threshold {
-            match &best_option {
-                None => best_option = Some((option.as_ref(), distance)),
-                Some((_, best_distance)) => {
-                    if distance < *best_distance {
-                        best_option = Some((option.as_ref(), distance));
-                    }
+        let option = option.as_ref();
+        let option_lower = option.to_ascii_lowercase();
+        if !option_lower.is_empty()
+            && (option_lower.contains(&input_lower) || input_lower.contains(&option_lower))
+        {
+            let len_diff = option.chars().count().abs_diff(input_len);
+            match best_substring_match {
+                None => best_substring_match = Some((option, len_diff)),
+                Some((_, best_len_diff)) if len_diff < best_len_diff => {
+                    best_substring_match = Some((option, len_diff));
                 }
+                _ => {}
             }
         }
     }
+    if let Some((option, _)) = best_substring_match {
+        return Some(option);
+    }
 
-    best_option.map(|(option, _)| option)
+    find_best_suggestion(input, options)
 }
 
 #[cfg(test)]
@@ -108,6 +306,54 @@ mod tests {
         assert_eq!(levenshtein_distance("abc", "xyz"), 3);
     }
 
+    #[test]
+    fn test_damerau_levenshtein_distance() {
+        assert_eq!(damerau_levenshtein_distance("", ""), 0);
+        assert_eq!(damerau_levenshtein_distance("a", ""), 1);
+        assert_eq!(damerau_levenshtein_distance("", "a"), 1);
+        assert_eq!(damerau_levenshtein_distance("abc", "abc"), 0);
+        assert_eq!(damerau_levenshtein_distance("kitten", "sitting"), 3);
+        // Transpositions count as a single edit.
+        assert_eq!(damerau_levenshtein_distance("vector", "vetcor"), 1);
+        assert_eq!(damerau_levenshtein_distance("teh", "the"), 1);
+        assert_eq!(damerau_levenshtein_distance("ab", "ba"), 1);
+        // Falls back to ordinary Levenshtein distance when there's no
+        // adjacent transposition to exploit.
+        assert_eq!(
+            damerau_levenshtein_distance("saturday", "sunday"),
+            levenshtein_distance("saturday", "sunday")
+        );
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_distance_limit() {
+        assert_eq!(damerau_levenshtein_distance_limit("", "", 0), Some(0));
+        assert_eq!(damerau_levenshtein_distance_limit("a", "", 1), Some(1));
+        assert_eq!(damerau_levenshtein_distance_limit("a", "", 0), None);
+        assert_eq!(
+            damerau_levenshtein_distance_limit("vector", "vextor", 1),
+            Some(1)
+        );
+        assert_eq!(
+            damerau_levenshtein_distance_limit("vector", "vextor", 0),
+            None
+        );
+        assert_eq!(
+            damerau_levenshtein_distance_limit("kitten", "sitting", 2),
+            None
+        );
+        // Lengths differ by more than the limit: bail out before scanning.
+        assert_eq!(
+            damerau_levenshtein_distance_limit("abc", "abcdefgh", 1),
+            None
+        );
+        // Matches the unrestricted distance whenever it is within limit.
+        assert_eq!(
+            damerau_levenshtein_distance_limit("saturday", "sunday", 3),
+            Some(damerau_levenshtein_distance("saturday", "sunday"))
+        );
+    }
+
     #[test]
     fn test_find_best_suggestion() {
         let options = vec!["vector", "id", "name", "column", "table"];
@@ -115,6 +361,9 @@ mod tests {
         assert_eq!(find_best_suggestion("vacter", &options), Some("vector"));
         assert_eq!(find_best_suggestion("vectr", &options), Some("vector"));
         assert_eq!(find_best_suggestion("tble", &options), Some("table"));
+        // A transposed typo of a six-letter column name is distance 1 under
+        // Damerau-Levenshtein, well within the threshold.
+        assert_eq!(find_best_suggestion("vetcor", &options), Some("vector"));
 
         // Should return None if no good match
         assert_eq!(find_best_suggestion("hello", &options), None);
@@ -130,4 +379,74 @@ mod tests {
             Some("vector")
         );
     }
+
+    #[test]
+    fn test_find_suggestions() {
+        let options = vec!["vector", "vendor", "id", "name", "column", "table"];
+
+        // Ranked ascending by distance.
+        assert_eq!(
+            find_suggestions("vecor", &options, 2, 2),
+            vec!["vector", "vendor"]
+        );
+
+        // `limit` caps the number of results even if more options qualify.
+        assert_eq!(find_suggestions("vecor", &options, 1, 2), vec!["vector"]);
+
+        // `max_distance` excludes options that are too far away.
+        assert_eq!(
+            find_suggestions("vecor", &options, 5, 0),
+            Vec::<&str>::new()
+        );
+
+        // Ties keep the options' original relative order.
+        assert_eq!(
+            find_suggestions("xy", &["ab", "cd"], 2, 2),
+            vec!["ab", "cd"]
+        );
+
+        // `find_best_suggestion` is a thin wrapper: limit = 1, threshold = input_len / 3.
+        assert_eq!(
+            find_suggestions("vacter", &options, 1, "vacter".chars().count() / 3),
+            vec![find_best_suggestion("vacter", &options).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_find_best_suggestion_smart() {
+        let options = vec!["vector", "id", "name", "column", "table"];
+
+        // Case-insensitive exact match wins outright, even with no edits otherwise.
+        assert_eq!(find_best_suggestion_smart("Vector", &options), Some("vector"));
+        assert_eq!(find_best_suggestion_smart("VECTOR", &options), Some("vector"));
+
+        // Substring in either direction counts as a near-match.
+        assert_eq!(find_best_suggestion_smart("vec", &options), Some("vector"));
+        assert_eq!(
+            find_best_suggestion_smart("vectors_column", &options),
+            Some("vector")
+        );
+
+        // Falls back to Damerau-Levenshtein when there's no case/substring shortcut.
+        assert_eq!(find_best_suggestion_smart("vacter", &options), Some("vector"));
+        assert_eq!(find_best_suggestion_smart("vextor", &options), Some("vector"));
+
+        // Still rejects unrelated input.
+        assert_eq!(find_best_suggestion_smart("hello", &options), None);
+        assert_eq!(find_best_suggestion_smart("", &options), None);
+
+        // An empty-string option is not a substring "near match" for every
+        // input: `"anything".contains("")` is vacuously true, but shouldn't
+        // cause an unrelated option to win over a genuine fallback match (or
+        // get returned at all for unrelated input).
+        let options_with_empty = vec!["", "vector"];
+        assert_eq!(
+            find_best_suggestion_smart("vacter", &options_with_empty),
+            Some("vector")
+        );
+        assert_eq!(
+            find_best_suggestion_smart("hello", &options_with_empty),
+            None
+        );
+    }
 }